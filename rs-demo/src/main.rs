@@ -3,7 +3,7 @@
 
 use core::arch::global_asm;
 use crate::syscall::{Syscall};
-use crate::system::{close, open, print, read, write_u8_slice, write_ptr, socket};
+use crate::system::{close, open, print, read, write_u8_slice, write_ptr, socket, try_read, Errno, info, arg_count, arg_get, delete, accept, SOCKET_FLAG_LISTEN, dup, STDOUT_FD};
 
 mod screen;
 mod syscall;
@@ -15,6 +15,17 @@ global_asm!(include_str!("../start.s")); // init stack pointer
 pub extern "C" fn main() -> ! {
     print(b"Running syscalls.\n");
 
+    let mut arg_buf = [0u8; 32];
+    let argc = arg_count();
+    if argc > 0 {
+        let n = arg_get(0, &mut arg_buf);
+        if n > 0 {
+            print(b"arg 0: ");
+            print(&arg_buf[..n as usize]);
+            print(b"\n");
+        }
+    }
+
     let writing_file = open(b"./file.txt", 0);
     if writing_file < 0 {
         print(b"open write file failed\n");
@@ -30,7 +41,7 @@ pub extern "C" fn main() -> ! {
         print(b"close write file failed\n");
     }
 
-    let socket = socket(b"localhost:9008");
+    let socket = socket(b"localhost:9008", 0);
     if socket < 0 {
         print(b"failed to bind socket\n");
     }
@@ -41,15 +52,20 @@ pub extern "C" fn main() -> ! {
     }
 
     let mut buf = [0u8; 32];
-    let n = read(socket, &mut buf);
-    if n == -64 {
-        print(b"no data in socket, try again\n");
-    } else if n < 0 {
-        print(b"read from socket failed\n");
-    }
+    let n = match try_read(socket, &mut buf) {
+        Ok(n) => n,
+        Err(Errno::WouldBlock) => {
+            print(b"no data in socket, try again\n");
+            0
+        }
+        Err(Errno::Unknown(_)) => {
+            print(b"read from socket failed\n");
+            0
+        }
+    };
 
     print(b"read from socket:\n");
-    print(&buf[..n as usize]);
+    print(&buf[..n]);
     print(b"\n");
 
     let c = close(socket);
@@ -57,6 +73,40 @@ pub extern "C" fn main() -> ! {
         print(b"close socket failed\n");
     }
 
+    let listen_fd = socket(b"localhost:9009", SOCKET_FLAG_LISTEN);
+    if listen_fd < 0 {
+        print(b"failed to listen on socket\n");
+    }
+
+    let conn_fd = accept(listen_fd);
+    if conn_fd < 0 {
+        print(b"accept failed\n");
+    } else {
+        let n = read(conn_fd, &mut buf);
+        if n < 0 {
+            print(b"read from accepted connection failed\n");
+        } else {
+            print(b"accepted connection said:\n");
+            print(&buf[..n as usize]);
+            print(b"\n");
+        }
+
+        let n = write_u8_slice(conn_fd, b"hello from Click-V\n");
+        if n < 0 {
+            print(b"write to accepted connection failed\n");
+        }
+
+        let c = close(conn_fd);
+        if c < 0 {
+            print(b"close accepted connection failed\n");
+        }
+    }
+
+    let c = close(listen_fd);
+    if c < 0 {
+        print(b"close listen socket failed\n");
+    }
+
     let reading_file = open(b"./file.txt", 0);
     if reading_file < 0 {
         print(b"open read file failed\n");
@@ -75,14 +125,31 @@ pub extern "C" fn main() -> ! {
     print(b"read from file:\n");
     print(&buf[..n as usize]);
 
+    let mut frame = [0u8; screen::SIZE as usize];
     let mut i = 0;
     while i < screen::SIZE {
-        screen::set_cell_by_index(i, screen::Color::from_index(i as u8 % screen::NUM_COLORS));
-        screen::draw_screen();
+        frame[i as usize] = screen::Color::from_index(i as u8 % screen::NUM_COLORS).to_ansi();
         i += 1;
     }
+    screen::blit(&frame);
     print(b"Updated pixels.\n");
 
+    let mut packed_frame = [0u8; screen::SIZE as usize];
+    let mut i = 0;
+    while i < screen::SIZE {
+        let fg = screen::Color::from_index(i as u8 % screen::NUM_COLORS);
+        let bg = screen::Color::from_index((i as u8 + 1) % screen::NUM_COLORS);
+        packed_frame[i as usize] = screen::pack_attr(&fg, &bg);
+        i += 1;
+    }
+    screen::blit(&packed_frame);
+    print(b"Updated pixels with packed attribute cells.\n");
+
+    let d = delete(b"./image.bin");
+    if d < 0 {
+        print(b"delete image file failed\n");
+    }
+
     let image_file = open(b"./image.bin", 0);
     if image_file < 0 {
         print(b"open write image file failed\n");
@@ -98,5 +165,32 @@ pub extern "C" fn main() -> ! {
         print(b"close write file failed\n");
     }
 
+    match info(b"./image.bin") {
+        Some(image_info) if image_info.size == screen::SIZE as u64 => {
+            print(b"image.bin written fully\n");
+        }
+        Some(_) => print(b"image.bin is the wrong size\n"),
+        None => print(b"failed to stat image.bin\n"),
+    }
+
+    // Redirect print() onto a file. Nothing below this point should rely
+    // on print() reaching the host's stdout anymore.
+    let log_file = open(b"./log.txt", 0);
+    if log_file < 0 {
+        print(b"open log file failed\n");
+    }
+
+    let d = dup(log_file, STDOUT_FD);
+    if d < 0 {
+        print(b"dup log file onto stdout failed\n");
+    }
+
+    print(b"redirected log output\n");
+
+    let c = close(log_file);
+    if c < 0 {
+        print(b"close log file failed\n");
+    }
+
     loop {}
 }
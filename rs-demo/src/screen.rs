@@ -34,6 +34,20 @@ impl Color {
         }
     }
 
+    fn to_index(&self) -> u8 {
+        match *self {
+            Color::Reset => 0,
+            Color::Black => 1,
+            Color::Red => 2,
+            Color::Green => 3,
+            Color::Yellow => 4,
+            Color::Blue => 5,
+            Color::Magenta => 6,
+            Color::Cyan => 7,
+            Color::White => 8,
+        }
+    }
+
     pub fn from_index(value: u8) -> Color {
         match value {
             0 => Color::Reset,
@@ -66,3 +80,27 @@ pub fn draw_screen() {
     }
 }
 
+/// Packs a foreground and background color into one attribute byte,
+/// VGA-style: low nibble is the foreground index, high nibble is the
+/// background index. Both halves get the full 4 bits since `NUM_COLORS`
+/// (9) needs all of them — there's no spare bit left over for a bold
+/// flag like real VGA attribute bytes have.
+pub fn pack_attr(fg: &Color, bg: &Color) -> u8 {
+    let fg_bits = fg.to_index() & 0x0F;
+    let bg_bits = (bg.to_index() & 0x0F) << 4;
+    fg_bits | bg_bits
+}
+
+/// Copies a whole frame into the framebuffer and issues exactly one
+/// `Draw` syscall, instead of one syscall per cell.
+pub fn blit(buf: &[u8]) {
+    unsafe {
+        let mut i = 0;
+        while i < buf.len() {
+            START_ADDR.add(i).write_volatile(buf[i]);
+            i += 1;
+        }
+    }
+    draw_screen();
+}
+
@@ -12,6 +12,12 @@ pub enum Syscall {
     Read,
     Write,
     Socket,
+    Info,
+    ArgCount,
+    ArgGet,
+    Accept,
+    Dup,
+    Delete,
 }
 
 impl Syscall {
@@ -27,6 +33,12 @@ impl Syscall {
             Syscall::Read => 13,
             Syscall::Write => 14,
             Syscall::Socket => 15,
+            Syscall::Info => 16,
+            Syscall::ArgCount => 17,
+            Syscall::ArgGet => 18,
+            Syscall::Accept => 19,
+            Syscall::Dup => 20,
+            Syscall::Delete => 21,
         }
     }
 }
@@ -1,46 +1,245 @@
 use core::panic::PanicInfo;
-use crate::syscall::{Syscall, syscall1, syscall2, syscall3, syscall5};
+use crate::syscall::{Syscall, syscall0, syscall1, syscall2, syscall3, syscall5};
+
+/// Error codes the ClickOS host returns as negated syscall results.
+///
+/// `from_code` maps a known host error number to a named variant; anything
+/// not yet mapped falls through to `Unknown` so callers can still recover
+/// the raw code instead of losing information.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Errno {
+    /// The call would have blocked (e.g. no data waiting on a socket yet).
+    WouldBlock,
+    Unknown(isize),
+}
+
+impl Errno {
+    pub fn from_code(code: isize) -> Errno {
+        match code {
+            64 => Errno::WouldBlock,
+            _ => Errno::Unknown(code),
+        }
+    }
+
+    pub fn to_code(&self) -> isize {
+        match *self {
+            Errno::WouldBlock => 64,
+            Errno::Unknown(code) => code,
+        }
+    }
+
+    /// Turns a raw syscall return value into a `Result`, following the
+    /// ClickOS convention of `>= 0` for success and `-errno` for failure.
+    pub fn from_ret(ret: isize) -> Result<usize, Errno> {
+        if ret >= 0 {
+            Ok(ret as usize)
+        } else {
+            Err(Errno::from_code(-ret))
+        }
+    }
+}
+
+fn to_raw_ret(result: Result<usize, Errno>) -> isize {
+    match result {
+        Ok(n) => n as isize,
+        Err(e) => -e.to_code(),
+    }
+}
+
+/// The kind of entry a `FileInfo` describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    File,
+    Dir,
+    Socket,
+    Unknown,
+}
+
+impl FileKind {
+    fn from_byte(value: u8) -> FileKind {
+        match value {
+            0 => FileKind::File,
+            1 => FileKind::Dir,
+            2 => FileKind::Socket,
+            _ => FileKind::Unknown,
+        }
+    }
+}
+
+/// File metadata filled in by the host for `Syscall::Info`.
+///
+/// Layout must match what ClickOS writes through the `stat_ptr` argument,
+/// so this stays `#[repr(C)]` rather than a regular Rust struct.
+#[repr(C)]
+pub struct RawFileInfo {
+    size: u64,
+    kind: u8,
+    mod_count: u32,
+}
+
+pub struct FileInfo {
+    pub size: u64,
+    pub kind: FileKind,
+    pub mod_count: u32,
+}
+
+pub fn try_info(path: &[u8]) -> Result<FileInfo, Errno> {
+    let mut raw = RawFileInfo { size: 0, kind: 0, mod_count: 0 };
+    let ret = unsafe {
+        syscall3(
+            Syscall::Info,
+            path.as_ptr() as isize,
+            path.len() as isize,
+            &mut raw as *mut RawFileInfo as isize,
+        )
+    };
+    Errno::from_ret(ret)?;
+    Ok(FileInfo {
+        size: raw.size,
+        kind: FileKind::from_byte(raw.kind),
+        mod_count: raw.mod_count,
+    })
+}
+
+pub fn info(path: &[u8]) -> Option<FileInfo> {
+    try_info(path).ok()
+}
+
+/// Number of command-line arguments the host passed to this program.
+pub fn try_arg_count() -> Result<usize, Errno> {
+    Errno::from_ret(unsafe { syscall0(Syscall::ArgCount) })
+}
+
+pub fn arg_count() -> isize {
+    to_raw_ret(try_arg_count())
+}
+
+/// Copies argument `index` into `buf`, returning the number of bytes
+/// written, or `Err(Errno)` on failure (e.g. index out of range).
+pub fn try_arg_get(index: isize, buf: &mut [u8]) -> Result<usize, Errno> {
+    Errno::from_ret(unsafe { syscall3(Syscall::ArgGet, index, buf.as_ptr() as isize, buf.len() as isize) })
+}
+
+pub fn arg_get(index: isize, buf: &mut [u8]) -> isize {
+    to_raw_ret(try_arg_get(index, buf))
+}
+
+/// Where `print()` currently sends its output: `None` for the host's
+/// `Syscall::Print`, or `Some(fd)` once `dup()` has retargeted
+/// `STDOUT_FD` onto a file or socket descriptor.
+static mut PRINT_TARGET_FD: Option<isize> = None;
 
 pub fn print(msg: &[u8]) {
     unsafe {
-        syscall2(Syscall::Print, msg.as_ptr() as isize, msg.len() as isize);
+        match PRINT_TARGET_FD {
+            Some(fd) => {
+                syscall3(Syscall::Write, fd, msg.as_ptr() as isize, msg.len() as isize);
+            }
+            None => {
+                syscall2(Syscall::Print, msg.as_ptr() as isize, msg.len() as isize);
+            }
+        }
     }
 }
 
-pub fn open(path_name: &[u8], flags: isize) -> isize {
-    unsafe {
+pub fn try_open(path_name: &[u8], flags: isize) -> Result<usize, Errno> {
+    Errno::from_ret(unsafe {
         syscall3(Syscall::Open, path_name.as_ptr() as isize, path_name.len() as isize, flags)
-    }
+    })
 }
 
-pub fn socket(address: &[u8]) -> isize {
-    unsafe {
-        syscall2(Syscall::Socket, address.as_ptr() as isize, address.len() as isize)
+pub fn open(path_name: &[u8], flags: isize) -> isize {
+    to_raw_ret(try_open(path_name, flags))
+}
+
+/// Passed as `flags` to `socket()` to mark the descriptor as passive
+/// (listening) instead of connecting to `address` right away.
+pub const SOCKET_FLAG_LISTEN: isize = 1;
+
+pub fn try_socket(address: &[u8], flags: isize) -> Result<usize, Errno> {
+    Errno::from_ret(unsafe {
+        syscall3(Syscall::Socket, address.as_ptr() as isize, address.len() as isize, flags)
+    })
+}
+
+pub fn socket(address: &[u8], flags: isize) -> isize {
+    to_raw_ret(try_socket(address, flags))
+}
+
+pub fn try_accept(listen_fd: isize) -> Result<usize, Errno> {
+    Errno::from_ret(unsafe { syscall1(Syscall::Accept, listen_fd) })
+}
+
+pub fn accept(listen_fd: isize) -> isize {
+    to_raw_ret(try_accept(listen_fd))
+}
+
+/// Conventional descriptor number for the implicit stream `print()`
+/// writes to, matching POSIX's `STDOUT_FILENO`. `dup`-ing a file or
+/// socket onto this descriptor redirects subsequent `print()` calls
+/// there, by updating `PRINT_TARGET_FD`.
+pub const STDOUT_FD: isize = 1;
+
+/// Redirects `new_fd` to point at whatever `old_fd` points at, like
+/// `dup2`. When `new_fd` is `STDOUT_FD`, this also retargets `print()`
+/// itself, since the host has no real file-descriptor table backing
+/// `Syscall::Print` for us to dup against.
+pub fn try_dup(old_fd: isize, new_fd: isize) -> Result<usize, Errno> {
+    let result = Errno::from_ret(unsafe { syscall2(Syscall::Dup, old_fd, new_fd) });
+    if result.is_ok() && new_fd == STDOUT_FD {
+        unsafe { PRINT_TARGET_FD = Some(new_fd) };
     }
+    result
+}
+
+pub fn dup(old_fd: isize, new_fd: isize) -> isize {
+    to_raw_ret(try_dup(old_fd, new_fd))
+}
+
+/// Removes the file at `path`, mirroring MOROS's `delete` syscall.
+///
+/// This covers the delete/unlink half of the "fresh file each run"
+/// problem; there's no `truncate(fd)` (the other half the request
+/// offered) since `delete` followed by `open` already gets `main.rs`'s
+/// image dump a zero-length `image.bin` without a second mechanism.
+pub fn try_delete(path: &[u8]) -> Result<usize, Errno> {
+    Errno::from_ret(unsafe { syscall2(Syscall::Delete, path.as_ptr() as isize, path.len() as isize) })
+}
+
+pub fn delete(path: &[u8]) -> isize {
+    to_raw_ret(try_delete(path))
+}
+
+pub fn try_close(fd: isize) -> Result<usize, Errno> {
+    Errno::from_ret(unsafe { syscall1(Syscall::Close, fd) })
 }
 
 pub fn close(fd: isize) -> isize {
-    unsafe {
-        syscall1(Syscall::Close, fd)
-    }
+    to_raw_ret(try_close(fd))
+}
+
+pub fn try_seek(fd: isize, offset: isize, whence: isize) -> Result<usize, Errno> {
+    Errno::from_ret(unsafe { syscall3(Syscall::Seek, fd, offset, whence) })
 }
 
 pub fn seek(fd: isize, offset: isize, whence: isize) -> isize {
-    unsafe {
-        syscall3(Syscall::Seek, fd, offset, whence)
-    }
+    to_raw_ret(try_seek(fd, offset, whence))
+}
+
+pub fn try_read(fd: isize, buf: &mut [u8]) -> Result<usize, Errno> {
+    Errno::from_ret(unsafe { syscall3(Syscall::Read, fd, buf.as_ptr() as isize, buf.len() as isize) })
 }
 
 pub fn read(fd: isize, buf: &mut [u8]) -> isize {
-    unsafe {
-        syscall3(Syscall::Read, fd, buf.as_ptr() as isize, buf.len() as isize)
-    }
+    to_raw_ret(try_read(fd, buf))
+}
+
+pub fn try_write_u8_slice(fd: isize, buf: &[u8]) -> Result<usize, Errno> {
+    Errno::from_ret(unsafe { syscall3(Syscall::Write, fd, buf.as_ptr() as isize, buf.len() as isize) })
 }
 
 pub fn write_u8_slice(fd: isize, buf: &[u8]) -> isize {
-    unsafe {
-        syscall3(Syscall::Write, fd, buf.as_ptr() as isize, buf.len() as isize)
-    }
+    to_raw_ret(try_write_u8_slice(fd, buf))
 }
 
 pub fn write_ptr(fd: isize, ptr: *const u8, count: usize) -> isize {